@@ -1,3 +1,8 @@
+// These modules are the engine's library surface; the CLI wiring that will
+// call into them hasn't landed yet, so allow it to sit unused rather than
+// dead_code-warn on every public item ahead of that.
+#![allow(dead_code)]
+
 use tracing::info;
 
 mod wifi;
@@ -0,0 +1,8 @@
+pub mod port_scanner;
+
+// Port-scan result and config types the CLI will wire up; unused for now
+// since main() doesn't call into this module yet.
+#[allow(unused_imports)]
+pub use port_scanner::{
+    CertificateInfo, PortRange, PortResult, PortScanner, PortState, Protocol, ScanInfo, TlsInfo,
+};
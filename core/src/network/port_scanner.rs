@@ -1,15 +1,17 @@
 //! Port scanning functionality
 
-use anyhow::{Result, Context};
-use std::net::{IpAddr, SocketAddr, TcpStream};
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use regex::Regex;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::net::UdpSocket;
+use tokio::net::{TcpStream as AsyncTcpStream, UdpSocket};
 use tokio::time::timeout;
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 pub struct PortScanner {
-    target: IpAddr,
+    targets: Vec<IpAddr>,
     ports: PortRange,
     timeout_duration: Duration,
     max_parallel: usize,
@@ -23,7 +25,7 @@ pub struct PortRange {
 impl PortScanner {
     pub fn new(target: IpAddr, start_port: u16, end_port: u16) -> Self {
         Self {
-            target,
+            targets: vec![target],
             ports: PortRange {
                 start: start_port,
                 end: end_port,
@@ -33,6 +35,35 @@ impl PortScanner {
         }
     }
 
+    /// Build a scanner over a mix of CIDR blocks, host IPs, and hostnames
+    pub fn from_targets(targets: &[String], start_port: u16, end_port: u16) -> Result<Self> {
+        let mut expanded = Vec::new();
+
+        for target in targets {
+            if target.contains('/') {
+                expanded.extend(expand_cidr(target)?);
+            } else if let Ok(ip) = target.parse::<IpAddr>() {
+                expanded.push(ip);
+            } else {
+                expanded.extend(resolve_host(target)?);
+            }
+        }
+
+        if expanded.is_empty() {
+            anyhow::bail!("no targets to scan");
+        }
+
+        Ok(Self {
+            targets: expanded,
+            ports: PortRange {
+                start: start_port,
+                end: end_port,
+            },
+            timeout_duration: Duration::from_millis(1000),
+            max_parallel: 100,
+        })
+    }
+
     pub fn with_timeout(mut self, duration: Duration) -> Self {
         self.timeout_duration = duration;
         self
@@ -43,70 +74,75 @@ impl PortScanner {
         self
     }
 
-    /// Perform TCP SYN scan (requires root/admin privileges)
+    /// Perform a TCP connect scan across all targets and ports
     pub async fn scan(&self) -> Result<Vec<PortResult>> {
-        let ports: Vec<u16> = (self.ports.start..=self.ports.end).collect();
-        
-        // Use rayon for parallel scanning
-        let results: Vec<PortResult> = ports
-            .par_iter()
-            .chunks(self.max_parallel)
-            .flat_map(|port_chunk| {
-                port_chunk
-                    .iter()
-                    .filter_map(|&&port| {
-                        match self.scan_tcp_port(port) {
-                            Ok(result) => Some(result),
-                            Err(_) => None,
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+        let tasks = self
+            .targets
+            .iter()
+            .flat_map(|&target| (self.ports.start..=self.ports.end).map(move |port| (target, port)));
+
+        let results = stream::iter(tasks)
+            .map(|(target, port)| self.scan_tcp_port(target, port))
+            .buffer_unordered(self.max_parallel)
+            .collect::<Vec<_>>()
+            .await;
 
         Ok(results)
     }
 
-    /// Scan a single TCP port using connect scan
-    fn scan_tcp_port(&self, port: u16) -> Result<PortResult> {
-        let socket_addr = SocketAddr::new(self.target, port);
-        
-        match TcpStream::connect_timeout(&socket_addr, self.timeout_duration) {
-            Ok(_stream) => {
-                // Port is open
-                let service = self.detect_service(port);
-                Ok(PortResult {
+    /// Scan a single TCP port on `target` using an async connect scan
+    async fn scan_tcp_port(&self, target: IpAddr, port: u16) -> PortResult {
+        let socket_addr = SocketAddr::new(target, port);
+
+        match timeout(self.timeout_duration, AsyncTcpStream::connect(socket_addr)).await {
+            Ok(Ok(stream)) => {
+                let (service, banner) = self.fingerprint_service(stream, port).await;
+                let tls = if is_tls_port(port) {
+                    self.grab_tls_info(target, port).await.ok().flatten()
+                } else {
+                    None
+                };
+                PortResult {
+                    target,
                     port,
                     state: PortState::Open,
-                    service,
-                    protocol: Protocol::TCP,
-                    banner: None,
-                })
-            }
-            Err(_) => {
-                // Port is closed or filtered
-                Ok(PortResult {
-                    port,
-                    state: PortState::Closed,
-                    service: None,
+                    service: service.or_else(|| self.detect_service(port)),
                     protocol: Protocol::TCP,
-                    banner: None,
-                })
+                    banner,
+                    tls,
+                }
             }
+            Ok(Err(err)) => PortResult {
+                target,
+                port,
+                state: classify_connect_error(&err),
+                service: None,
+                protocol: Protocol::TCP,
+                banner: None,
+                tls: None,
+            },
+            Err(_elapsed) => PortResult {
+                target,
+                port,
+                state: PortState::Filtered,
+                service: None,
+                protocol: Protocol::TCP,
+                banner: None,
+                tls: None,
+            },
         }
     }
 
-    /// Scan UDP port
-    pub async fn scan_udp_port(&self, port: u16) -> Result<PortResult> {
+    /// Scan a UDP port, sending a protocol-appropriate probe payload
+    pub async fn scan_udp_port(&self, target: IpAddr, port: u16) -> Result<PortResult> {
         let socket = UdpSocket::bind("0.0.0.0:0").await
             .context("Failed to bind UDP socket")?;
 
-        let target_addr = SocketAddr::new(self.target, port);
+        let target_addr = SocketAddr::new(target, port);
+        let payload = udp_probe_payload(port);
 
-        // Send empty UDP packet
-        socket.send_to(&[], target_addr).await?;
+        socket.send_to(&payload, target_addr).await?;
 
-        // Try to receive response
         let mut buf = [0u8; 1024];
         let result = timeout(
             self.timeout_duration,
@@ -114,20 +150,59 @@ impl PortScanner {
         ).await;
 
         let state = match result {
-            Ok(Ok(_)) => PortState::Open,
-            Ok(Err(_)) => PortState::Closed,
-            Err(_) => PortState::OpenFiltered, // No response - could be open or filtered
+            Ok(Ok((n, _))) => {
+                if is_plausible_udp_reply(port, &buf[..n]) {
+                    PortState::Open
+                } else {
+                    PortState::OpenFiltered
+                }
+            }
+            Ok(Err(_)) => PortState::Closed, // ICMP port-unreachable surfaces as a recv error
+            Err(_) => PortState::OpenFiltered, // No response at all - could be open or filtered
         };
 
         Ok(PortResult {
+            target,
             port,
             state,
             service: self.detect_service(port),
             protocol: Protocol::UDP,
             banner: None,
+            tls: None,
         })
     }
 
+    /// Fingerprint the service behind an already-open `stream`, nmap style
+    async fn fingerprint_service(
+        &self,
+        mut stream: AsyncTcpStream,
+        port: u16,
+    ) -> (Option<String>, Option<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let probe = SERVICE_PROBES
+            .iter()
+            .find(|probe| probe.port == Some(port))
+            .unwrap_or_else(|| SERVICE_PROBES.last().expect("NULL probe always present"));
+
+        if let Some(payload) = probe.payload {
+            if stream.write_all(payload).await.is_err() {
+                return (None, None);
+            }
+        }
+
+        let mut buf = [0u8; 2048];
+        let n = match timeout(self.timeout_duration, stream.read(&mut buf)).await {
+            Ok(Ok(n)) if n > 0 => n,
+            _ => return (None, None),
+        };
+
+        let banner = String::from_utf8_lossy(&buf[..n]).to_string();
+        let service = SERVICE_PROBES.iter().find_map(|probe| match_signatures(probe, &banner));
+
+        (service, Some(banner))
+    }
+
     /// Detect common services by port number
     fn detect_service(&self, port: u16) -> Option<String> {
         let service = match port {
@@ -157,40 +232,83 @@ impl PortScanner {
     }
 
     /// Perform banner grabbing on open port
-    pub async fn grab_banner(&self, port: u16) -> Result<Option<String>> {
-        let socket_addr = SocketAddr::new(self.target, port);
-        
-        match timeout(
-            self.timeout_duration,
-            TcpStream::connect(socket_addr)
-        ).await {
-            Ok(Ok(mut stream)) => {
-                // Try to read banner
-                use std::io::Read;
-                stream.set_read_timeout(Some(self.timeout_duration))?;
-                
-                let mut buffer = [0u8; 1024];
-                match stream.read(&mut buffer) {
-                    Ok(n) if n > 0 => {
-                        let banner = String::from_utf8_lossy(&buffer[..n]).to_string();
-                        Ok(Some(banner))
-                    }
-                    _ => Ok(None)
-                }
-            }
-            _ => Ok(None)
+    pub async fn grab_banner(&self, target: IpAddr, port: u16) -> Result<Option<String>> {
+        use tokio::io::AsyncReadExt;
+
+        let socket_addr = SocketAddr::new(target, port);
+
+        let mut stream = match timeout(self.timeout_duration, AsyncTcpStream::connect(socket_addr)).await {
+            Ok(Ok(stream)) => stream,
+            _ => return Ok(None),
+        };
+
+        let mut buffer = [0u8; 1024];
+        match timeout(self.timeout_duration, stream.read(&mut buffer)).await {
+            Ok(Ok(n)) if n > 0 => Ok(Some(String::from_utf8_lossy(&buffer[..n]).to_string())),
+            _ => Ok(None),
         }
     }
 
+    /// Perform a TLS handshake against `target:port` and report its certificate info
+    pub async fn grab_tls_info(&self, target: IpAddr, port: u16) -> Result<Option<TlsInfo>> {
+        use rustls::pki_types::ServerName;
+        use tokio_rustls::TlsConnector;
+
+        let socket_addr = SocketAddr::new(target, port);
+        let tcp = match timeout(self.timeout_duration, AsyncTcpStream::connect(socket_addr)).await {
+            Ok(Ok(stream)) => stream,
+            _ => return Ok(None),
+        };
+
+        let config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoCertVerification))
+            .with_no_client_auth();
+
+        let connector = TlsConnector::from(Arc::new(config));
+        let server_name = ServerName::IpAddress(target.into());
+
+        let tls_stream = match timeout(self.timeout_duration, connector.connect(server_name, tcp)).await {
+            Ok(Ok(stream)) => stream,
+            _ => return Ok(None),
+        };
+
+        let (_, session) = tls_stream.get_ref();
+        let protocol_version = session
+            .protocol_version()
+            .map(|version| format!("{version:?}"))
+            .unwrap_or_else(|| "unknown".to_string());
+        let cipher_suite = session
+            .negotiated_cipher_suite()
+            .map(|suite| format!("{:?}", suite.suite()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let alpn = session
+            .alpn_protocol()
+            .map(|proto| String::from_utf8_lossy(proto).to_string());
+
+        let leaf_cert = session
+            .peer_certificates()
+            .and_then(|certs| certs.first())
+            .context("TLS handshake completed but presented no certificate")?;
+
+        Ok(Some(TlsInfo {
+            protocol_version,
+            cipher_suite,
+            alpn,
+            certificate: parse_certificate(leaf_cert)?,
+        }))
+    }
+
     /// Get scan statistics
     pub fn get_scan_info(&self) -> ScanInfo {
-        let total_ports = (self.ports.end - self.ports.start + 1) as usize;
+        let ports_per_target = (self.ports.end - self.ports.start + 1) as usize;
+        let total_ports = ports_per_target * self.targets.len();
         let estimated_duration = Duration::from_millis(
             (total_ports as u64 * self.timeout_duration.as_millis() as u64) / self.max_parallel as u64
         );
 
         ScanInfo {
-            target: self.target,
+            targets: self.targets.clone(),
             port_range: format!("{}-{}", self.ports.start, self.ports.end),
             total_ports,
             estimated_duration_seconds: estimated_duration.as_secs(),
@@ -199,13 +317,298 @@ impl PortScanner {
     }
 }
 
+/// Smallest CIDR prefix we'll expand eagerly (largest block size, 65536 addresses).
+const MIN_CIDR_PREFIX: u32 = 16;
+
+/// Expand a CIDR block (e.g. `192.168.1.0/24`) into every address it covers.
+fn expand_cidr(cidr: &str) -> Result<Vec<IpAddr>> {
+    let (base, prefix_str) = cidr
+        .split_once('/')
+        .with_context(|| format!("missing '/' in CIDR block: {cidr}"))?;
+
+    let prefix: u32 = prefix_str
+        .parse()
+        .with_context(|| format!("invalid CIDR prefix in {cidr}"))?;
+    anyhow::ensure!(prefix <= 32, "CIDR prefix must be 0-32, got {prefix}");
+    anyhow::ensure!(
+        prefix >= MIN_CIDR_PREFIX,
+        "CIDR block {cidr} is too large to scan (minimum prefix is /{MIN_CIDR_PREFIX})"
+    );
+
+    let base_ip: Ipv4Addr = base
+        .parse()
+        .with_context(|| format!("invalid CIDR base address: {base}"))?;
+
+    let base_u32 = u32::from(base_ip);
+    let mask = if prefix == 0 { 0 } else { !0u32 << (32 - prefix) };
+    let network = base_u32 & mask;
+    let broadcast = network | !mask;
+
+    Ok((network..=broadcast)
+        .map(|addr| IpAddr::V4(Ipv4Addr::from(addr)))
+        .collect())
+}
+
+/// Resolve a hostname to every address it has (both A and AAAA records)
+fn resolve_host(host: &str) -> Result<Vec<IpAddr>> {
+    let addresses: Vec<IpAddr> = (host, 0)
+        .to_socket_addrs()
+        .with_context(|| format!("failed to resolve host: {host}"))?
+        .map(|socket_addr| socket_addr.ip())
+        .collect();
+
+    if addresses.is_empty() {
+        anyhow::bail!("host {host} resolved to no addresses");
+    }
+
+    Ok(addresses)
+}
+
+/// One service-fingerprint probe, mirroring nmap's `nmap-service-probes` table
+struct ServiceProbe {
+    /// Port this probe's payload is tailored for; `None` marks the NULL probe.
+    port: Option<u16>,
+    payload: Option<&'static [u8]>,
+    signatures: &'static [(&'static str, &'static str)],
+}
+
+const SERVICE_PROBES: &[ServiceProbe] = &[
+    ServiceProbe {
+        port: Some(80),
+        payload: Some(b"GET / HTTP/1.0\r\n\r\n"),
+        signatures: &[(r"^HTTP/\d\.\d \d{3}", "HTTP"), (r"(?i)server:\s*nginx", "nginx")],
+    },
+    ServiceProbe {
+        port: Some(8080),
+        payload: Some(b"GET / HTTP/1.0\r\n\r\n"),
+        signatures: &[(r"^HTTP/\d\.\d \d{3}", "HTTP-Proxy")],
+    },
+    ServiceProbe {
+        port: Some(25),
+        payload: Some(b"EHLO scan\r\n"),
+        signatures: &[(r"^220[ -]", "SMTP")],
+    },
+    ServiceProbe {
+        port: Some(21),
+        payload: None,
+        signatures: &[(r"^220[ -]", "FTP")],
+    },
+    ServiceProbe {
+        port: Some(22),
+        payload: None,
+        signatures: &[(r"^SSH-\d\.\d", "SSH")],
+    },
+    ServiceProbe {
+        // NULL probe: no payload, just read whatever the server says first.
+        port: None,
+        payload: None,
+        signatures: &[],
+    },
+];
+
+/// Check `banner` against each of `probe`'s signatures
+fn match_signatures(probe: &ServiceProbe, banner: &str) -> Option<String> {
+    probe.signatures.iter().find_map(|(pattern, service)| {
+        Regex::new(pattern)
+            .ok()
+            .filter(|regex| regex.is_match(banner))
+            .map(|_| service.to_string())
+    })
+}
+
+/// Protocol-appropriate UDP probe payload for well-known ports
+fn udp_probe_payload(port: u16) -> Vec<u8> {
+    match port {
+        53 => dns_probe(),
+        123 => ntp_probe(),
+        161 => snmp_probe(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check whether `reply` looks like a genuine response to the probe we sent on `port`
+fn is_plausible_udp_reply(port: u16, reply: &[u8]) -> bool {
+    match port {
+        53 => is_dns_reply(reply),
+        123 => is_ntp_reply(reply),
+        161 => is_snmp_reply(reply),
+        _ => !reply.is_empty(),
+    }
+}
+
+/// Minimal DNS query asking the root zone for its NS records
+fn dns_probe() -> Vec<u8> {
+    vec![
+        0x12, 0x34, // transaction ID
+        0x01, 0x00, // flags: standard query, recursion desired
+        0x00, 0x01, // QDCOUNT = 1
+        0x00, 0x00, // ANCOUNT
+        0x00, 0x00, // NSCOUNT
+        0x00, 0x00, // ARCOUNT
+        0x00, // QNAME: root
+        0x00, 0x02, // QTYPE: NS
+        0x00, 0x01, // QCLASS: IN
+    ]
+}
+
+fn is_dns_reply(reply: &[u8]) -> bool {
+    reply.len() >= 12 && reply[0] == 0x12 && reply[1] == 0x34 && reply[2] & 0x80 != 0
+}
+
+/// Minimal NTP client request: LI=0, VN=3, Mode=3 (client), rest zeroed.
+fn ntp_probe() -> Vec<u8> {
+    let mut packet = [0u8; 48];
+    packet[0] = 0x1B;
+    packet.to_vec()
+}
+
+fn is_ntp_reply(reply: &[u8]) -> bool {
+    reply.len() >= 48 && matches!(reply[0] & 0x07, 2 | 4) // Mode: symmetric-passive or server
+}
+
+/// SNMPv1 GetRequest for `sysDescr.0` under the `public` community, BER-encoded
+fn snmp_probe() -> Vec<u8> {
+    vec![
+        0x30, 38, // SEQUENCE (message)
+        0x02, 0x01, 0x00, // INTEGER version = 0 (SNMPv1)
+        0x04, 0x06, b'p', b'u', b'b', b'l', b'i', b'c', // OCTET STRING community
+        0xA0, 25, // GetRequest-PDU
+        0x02, 0x01, 0x01, // request-id
+        0x02, 0x01, 0x00, // error-status
+        0x02, 0x01, 0x00, // error-index
+        0x30, 14, // varbind list
+        0x30, 12, // varbind
+        0x06, 0x08, 0x2B, 0x06, 0x01, 0x02, 0x01, 0x01, 0x01, 0x00, // OID
+        0x05, 0x00, // NULL value
+    ]
+}
+
+fn is_snmp_reply(reply: &[u8]) -> bool {
+    reply.first() == Some(&0x30)
+}
+
+/// Well-known ports that speak TLS, worth a `grab_tls_info` follow-up on open
+const TLS_PORTS: &[u16] = &[443, 465, 636, 993, 995, 8443];
+
+fn is_tls_port(port: u16) -> bool {
+    TLS_PORTS.contains(&port)
+}
+
+/// Map a failed `connect`/`connect_timeout` into `Closed` or `Filtered`
+fn classify_connect_error(err: &std::io::Error) -> PortState {
+    use std::io::ErrorKind;
+
+    match err.kind() {
+        ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset => PortState::Closed,
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => PortState::Filtered,
+        _ => {
+            // Some platforms (e.g. macOS) surface a refused/reset connection
+            // as a bare OS error rather than a matching `ErrorKind`.
+            let message = err.to_string().to_lowercase();
+            if message.contains("refused") || message.contains("reset") {
+                PortState::Closed
+            } else {
+                PortState::Filtered
+            }
+        }
+    }
+}
+
+/// Extract the leaf certificate's subject, SANs, and expiry from its DER encoding
+fn parse_certificate(der: &rustls::pki_types::CertificateDer<'_>) -> Result<CertificateInfo> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref())
+        .context("failed to parse leaf certificate")?;
+
+    let subject = cert.subject().to_string();
+    let subject_alt_names = cert
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|ext| ext.value.general_names.iter().map(|name| name.to_string()).collect())
+        .unwrap_or_default();
+    let not_after = cert.validity().not_after.to_string();
+
+    Ok(CertificateInfo {
+        subject,
+        subject_alt_names,
+        not_after,
+    })
+}
+
+/// Accepts any certificate chain; we're fingerprinting, not trusting, the host.
+#[derive(Debug)]
+struct NoCertVerification;
+
+impl rustls::client::danger::ServerCertVerifier for NoCertVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &rustls::pki_types::CertificateDer<'_>,
+        _dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &rustls::pki_types::CertificateDer<'_>,
+        _dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        vec![
+            rustls::SignatureScheme::RSA_PKCS1_SHA256,
+            rustls::SignatureScheme::RSA_PKCS1_SHA384,
+            rustls::SignatureScheme::RSA_PKCS1_SHA512,
+            rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
+            rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
+            rustls::SignatureScheme::RSA_PSS_SHA256,
+            rustls::SignatureScheme::RSA_PSS_SHA384,
+            rustls::SignatureScheme::RSA_PSS_SHA512,
+            rustls::SignatureScheme::ED25519,
+        ]
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PortResult {
+    pub target: IpAddr,
     pub port: u16,
     pub state: PortState,
     pub service: Option<String>,
     pub protocol: Protocol,
     pub banner: Option<String>,
+    pub tls: Option<TlsInfo>,
+}
+
+/// TLS/certificate details gathered by `grab_tls_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInfo {
+    pub protocol_version: String,
+    pub cipher_suite: String,
+    pub alpn: Option<String>,
+    pub certificate: CertificateInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub subject_alt_names: Vec<String>,
+    pub not_after: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -217,6 +620,7 @@ pub enum PortState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(clippy::upper_case_acronyms)] // TCP/UDP are the standard names, not abbreviations to rename
 pub enum Protocol {
     TCP,
     UDP,
@@ -224,7 +628,7 @@ pub enum Protocol {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ScanInfo {
-    pub target: IpAddr,
+    pub targets: Vec<IpAddr>,
     pub port_range: String,
     pub total_ports: usize,
     pub estimated_duration_seconds: u64,
@@ -259,7 +663,7 @@ mod tests {
         assert_eq!(scanner.detect_service(80), Some("HTTP".to_string()));
         assert_eq!(scanner.detect_service(443), Some("HTTPS".to_string()));
         assert_eq!(scanner.detect_service(22), Some("SSH".to_string()));
-        assert_eq!(scanner.detect_service(99999), None);
+        assert_eq!(scanner.detect_service(54321), None);
     }
 
     #[tokio::test]
@@ -274,4 +678,136 @@ mod tests {
         assert_eq!(info.total_ports, 1000);
         assert_eq!(info.port_range, "1-1000");
     }
+
+    #[test]
+    fn test_expand_cidr() {
+        let addresses = expand_cidr("192.168.1.0/30").unwrap();
+        assert_eq!(
+            addresses,
+            vec![
+                IpAddr::from_str("192.168.1.0").unwrap(),
+                IpAddr::from_str("192.168.1.1").unwrap(),
+                IpAddr::from_str("192.168.1.2").unwrap(),
+                IpAddr::from_str("192.168.1.3").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_cidr_rejects_blocks_larger_than_min_prefix() {
+        assert!(expand_cidr("10.0.0.0/8").is_err());
+    }
+
+    #[test]
+    fn test_from_targets_mixes_cidr_and_hosts() {
+        let scanner = PortScanner::from_targets(
+            &["10.0.0.0/30".to_string(), "8.8.8.8".to_string()],
+            1,
+            10,
+        )
+        .unwrap();
+
+        assert_eq!(scanner.targets.len(), 5);
+        assert!(scanner.targets.contains(&IpAddr::from_str("8.8.8.8").unwrap()));
+    }
+
+    #[test]
+    fn test_classify_connect_error() {
+        use std::io::{Error, ErrorKind};
+
+        assert_eq!(
+            classify_connect_error(&Error::from(ErrorKind::ConnectionRefused)),
+            PortState::Closed
+        );
+        assert_eq!(
+            classify_connect_error(&Error::from(ErrorKind::ConnectionReset)),
+            PortState::Closed
+        );
+        assert_eq!(
+            classify_connect_error(&Error::from(ErrorKind::TimedOut)),
+            PortState::Filtered
+        );
+        assert_eq!(
+            classify_connect_error(&Error::from(ErrorKind::WouldBlock)),
+            PortState::Filtered
+        );
+    }
+
+    #[test]
+    fn test_resolve_host_localhost() {
+        let addresses = resolve_host("localhost").unwrap();
+        assert!(!addresses.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_host_rejects_unresolvable_name() {
+        let err = resolve_host("this-host-does-not-exist.invalid");
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_udp_probe_payload_known_ports() {
+        assert_eq!(udp_probe_payload(53), dns_probe());
+        assert_eq!(udp_probe_payload(123), ntp_probe());
+        assert_eq!(udp_probe_payload(161), snmp_probe());
+        assert!(udp_probe_payload(9999).is_empty());
+    }
+
+    #[test]
+    fn test_is_plausible_udp_reply_dns() {
+        let reply = [0x12, 0x34, 0x81, 0x80, 0, 1, 0, 1, 0, 0, 0, 0];
+        assert!(is_plausible_udp_reply(53, &reply));
+        assert!(!is_plausible_udp_reply(53, &[0x00, 0x00]));
+    }
+
+    #[test]
+    fn test_is_plausible_udp_reply_ntp() {
+        let mut reply = [0u8; 48];
+        reply[0] = 0x24; // LI=0, VN=4, Mode=4 (server)
+        assert!(is_plausible_udp_reply(123, &reply));
+        assert!(!is_plausible_udp_reply(123, &[0u8; 10]));
+    }
+
+    #[test]
+    fn test_is_plausible_udp_reply_unknown_port_accepts_any_reply() {
+        assert!(is_plausible_udp_reply(9999, &[0x01]));
+        assert!(!is_plausible_udp_reply(9999, &[]));
+    }
+
+    #[test]
+    fn test_match_signatures_identifies_http() {
+        let probe = SERVICE_PROBES.iter().find(|p| p.port == Some(80)).unwrap();
+        assert_eq!(
+            match_signatures(probe, "HTTP/1.1 200 OK\r\nServer: nginx\r\n"),
+            Some("HTTP".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_signatures_no_match_returns_none() {
+        let probe = SERVICE_PROBES.iter().find(|p| p.port == Some(22)).unwrap();
+        assert_eq!(match_signatures(probe, "not a banner"), None);
+    }
+
+    #[test]
+    fn test_is_tls_port() {
+        assert!(is_tls_port(443));
+        assert!(is_tls_port(993));
+        assert!(!is_tls_port(80));
+    }
+
+    #[test]
+    fn test_parse_certificate_extracts_subject_and_sans() {
+        let der = rustls::pki_types::CertificateDer::from(
+            include_bytes!("testdata/test_cert.der").to_vec(),
+        );
+        let info = parse_certificate(&der).unwrap();
+
+        assert_eq!(info.subject, "CN=test.example.com");
+        assert_eq!(
+            info.subject_alt_names,
+            vec!["DNSName(test.example.com)".to_string(), "DNSName(alt.example.com)".to_string()]
+        );
+        assert!(info.not_after.contains("2036"));
+    }
 }
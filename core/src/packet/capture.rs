@@ -1,18 +1,251 @@
 //! Packet capture functionality
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+use super::eapol::{self, EapolFrame};
+
+/// DLT_IEEE802_11_RADIOTAP, the link-layer type monitor-mode interfaces deliver.
+const RADIOTAP_DLT: i32 = 127;
+
+/// A complete WPA/WPA2 4-way handshake captured between an AP and a client.
+#[derive(Debug, Clone)]
+pub struct CapturedHandshake {
+    pub bssid: String,
+    pub client_mac: String,
+    pub frames: Vec<EapolFrame>,
+}
+
+#[derive(Default)]
+struct PartialHandshake {
+    frames: [Option<EapolFrame>; 4],
+}
+
+impl PartialHandshake {
+    fn insert(&mut self, frame: EapolFrame) {
+        // A fresh message 1 carries a new ANonce, i.e. a new handshake
+        // attempt for this BSSID/client pair - drop any frames left over
+        // from a prior incomplete attempt so they don't get stitched onto
+        // this one.
+        if frame.message_num == 1 {
+            self.frames = Default::default();
+        }
+        let idx = (frame.message_num - 1) as usize;
+        self.frames[idx] = Some(frame);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.frames.iter().all(Option::is_some)
+    }
+
+    fn into_frames(self) -> Vec<EapolFrame> {
+        self.frames.into_iter().flatten().collect()
+    }
+}
+
+type ClientsByBssid = Arc<Mutex<HashMap<String, HashSet<String>>>>;
 
 pub struct PacketCapture {
     interface: String,
+    pcap_dir: PathBuf,
+    clients: ClientsByBssid,
+    capture_task: Option<tokio::task::JoinHandle<Result<()>>>,
 }
 
 impl PacketCapture {
     pub fn new(interface: String) -> Self {
-        Self { interface }
+        Self {
+            interface,
+            pcap_dir: PathBuf::from("."),
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            capture_task: None,
+        }
+    }
+
+    /// Directory completed handshakes are written to, as `<bssid>_<client>.pcap`.
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.pcap_dir = dir.into();
+        self
+    }
+
+    /// Start sniffing EAPOL frames on `self.interface` for completed 4-way handshakes
+    pub async fn start_capture(&mut self) -> Result<mpsc::Receiver<CapturedHandshake>> {
+        let interface = self.interface.clone();
+        let pcap_dir = self.pcap_dir.clone();
+        let clients = self.clients.clone();
+        let (tx, rx) = mpsc::channel(16);
+
+        let task =
+            tokio::task::spawn_blocking(move || Self::capture_loop(interface, pcap_dir, clients, tx));
+        self.capture_task = Some(task);
+
+        Ok(rx)
+    }
+
+    /// Wait for the background capture task to finish.
+    pub async fn join(&mut self) -> Result<()> {
+        if let Some(task) = self.capture_task.take() {
+            task.await.context("packet capture task panicked")??;
+        }
+        Ok(())
+    }
+
+    /// Client MACs observed associating with `bssid` so far.
+    pub fn clients_for(&self, bssid: &str) -> Vec<String> {
+        self.clients
+            .lock()
+            .unwrap()
+            .get(bssid)
+            .map(|macs| macs.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Fill in `Network::clients` for every scanned network.
+    pub fn populate_clients(&self, networks: &mut [crate::wifi::Network]) {
+        let clients = self.clients.lock().unwrap();
+        for network in networks {
+            if let Some(macs) = clients.get(&network.bssid) {
+                network.clients = macs.iter().cloned().collect();
+            }
+        }
     }
 
-    pub async fn start_capture(&mut self) -> Result<()> {
-        // TODO: Implement packet capture
+    fn capture_loop(
+        interface: String,
+        pcap_dir: PathBuf,
+        clients: ClientsByBssid,
+        tx: mpsc::Sender<CapturedHandshake>,
+    ) -> Result<()> {
+        let mut cap = pcap::Capture::from_device(interface.as_str())
+            .with_context(|| format!("no such capture device: {interface}"))?
+            .promisc(true)
+            .snaplen(65535)
+            .timeout(1000)
+            .open()
+            .context("failed to open monitor-mode capture")?;
+
+        if cap.get_datalink().0 != RADIOTAP_DLT {
+            anyhow::bail!(
+                "interface {interface} is not delivering radiotap-framed 802.11 \
+                 (run InterfaceManager::enable_monitor_mode first)"
+            );
+        }
+
+        let mut handshakes: HashMap<(String, String), PartialHandshake> = HashMap::new();
+
+        loop {
+            let packet = match cap.next_packet() {
+                Ok(packet) => packet,
+                Err(pcap::Error::TimeoutExpired) => continue,
+                Err(pcap::Error::NoMorePackets) => break,
+                Err(err) => return Err(err).context("error reading packet"),
+            };
+
+            let Some((bssid, client_mac, frame)) = eapol::parse_eapol_frame(&packet) else {
+                continue;
+            };
+
+            clients
+                .lock()
+                .unwrap()
+                .entry(bssid.clone())
+                .or_default()
+                .insert(client_mac.clone());
+
+            let key = (bssid.clone(), client_mac.clone());
+            let partial = handshakes.entry(key.clone()).or_default();
+            partial.insert(frame);
+
+            if partial.is_complete() {
+                let partial = handshakes.remove(&key).unwrap();
+                let handshake = CapturedHandshake {
+                    bssid,
+                    client_mac,
+                    frames: partial.into_frames(),
+                };
+
+                if let Err(err) = write_pcap(&pcap_dir, &handshake) {
+                    tracing::warn!("failed to write handshake pcap: {err:#}");
+                }
+
+                if tx.blocking_send(handshake).is_err() {
+                    break;
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+fn write_pcap(dir: &std::path::Path, handshake: &CapturedHandshake) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let file_name = format!("{}_{}.pcap", handshake.bssid, handshake.client_mac).replace(':', "");
+    let path = dir.join(file_name);
+
+    let dead_cap = pcap::Capture::dead(pcap::Linktype(RADIOTAP_DLT))?;
+    let mut savefile = dead_cap.savefile(&path)?;
+    for frame in &handshake.frames {
+        let header = frame.to_packet_header();
+        savefile.write(&pcap::Packet::new(&header, &frame.raw));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_frame(message_num: u8) -> EapolFrame {
+        EapolFrame {
+            message_num,
+            key_info: 0,
+            replay_counter: message_num as u64,
+            key_nonce: [0u8; 32],
+            key_mic: [0u8; 16],
+            eapol_frame: Vec::new(),
+            raw: Vec::new(),
+            ts_secs: 0,
+            ts_usec: 0,
+            orig_len: 0,
+        }
+    }
+
+    #[test]
+    fn test_partial_handshake_completes_after_all_four_messages() {
+        let mut partial = PartialHandshake::default();
+        assert!(!partial.is_complete());
+
+        for message_num in 1..=4 {
+            partial.insert(dummy_frame(message_num));
+        }
+
+        assert!(partial.is_complete());
+        let frames = partial.into_frames();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(
+            frames.iter().map(|frame| frame.message_num).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_partial_handshake_resets_stale_frames_on_new_message1() {
+        let mut partial = PartialHandshake::default();
+        partial.insert(dummy_frame(2));
+        partial.insert(dummy_frame(3));
+        assert!(!partial.is_complete());
+
+        // A fresh message 1 means a new handshake attempt - the stale
+        // messages 2/3 from the prior attempt should not carry over.
+        partial.insert(dummy_frame(1));
+
+        assert!(partial.frames[0].is_some());
+        assert!(partial.frames[1].is_none());
+        assert!(partial.frames[2].is_none());
+    }
+}
@@ -0,0 +1,279 @@
+//! Parsing of radiotap-framed 802.11 data frames down to EAPOL-Key messages
+
+const LLC_SNAP_HEADER: [u8; 6] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x00];
+const ETHERTYPE_EAPOL: u16 = 0x888E;
+const EAPOL_TYPE_KEY: u8 = 3;
+
+const KEY_TYPE_PAIRWISE: u16 = 1 << 3;
+const KEY_INSTALL: u16 = 1 << 6;
+const KEY_ACK: u16 = 1 << 7;
+const KEY_MIC: u16 = 1 << 8;
+const KEY_SECURE: u16 = 1 << 9;
+
+/// Byte offset and length of the Key MIC field within `EapolFrame::eapol_frame`.
+pub const MIC_FIELD_RANGE: std::ops::Range<usize> = 81..97;
+
+/// A single EAPOL-Key frame belonging to a WPA/WPA2 4-way handshake.
+#[derive(Debug, Clone)]
+pub struct EapolFrame {
+    pub message_num: u8,
+    pub key_info: u16,
+    pub replay_counter: u64,
+    pub key_nonce: [u8; 32],
+    pub key_mic: [u8; 16],
+    /// The EAPOL frame alone (version/type/length header through key data),
+    /// i.e. what the MIC in `key_mic` is computed over with that field
+    /// zeroed. Used for offline MIC verification against a candidate PSK.
+    pub eapol_frame: Vec<u8>,
+    /// The full radiotap + 802.11 + LLC/SNAP + EAPOL frame as captured, for
+    /// writing back out to a `.pcap` file.
+    pub raw: Vec<u8>,
+    pub ts_secs: i64,
+    pub ts_usec: i64,
+    pub orig_len: u32,
+}
+
+impl EapolFrame {
+    pub fn to_packet_header(&self) -> pcap::PacketHeader {
+        pcap::PacketHeader {
+            ts: libc::timeval {
+                tv_sec: self.ts_secs as libc::time_t,
+                tv_usec: self.ts_usec as libc::suseconds_t,
+            },
+            caplen: self.raw.len() as u32,
+            len: self.orig_len,
+        }
+    }
+}
+
+/// Map an EAPOL-Key `key_info` field to its position (1-4) in the 4-way
+/// handshake, per IEEE 802.11i. Returns `None` for group-key or malformed
+/// key-info combinations we don't care about.
+pub fn classify_eapol_message(key_info: u16) -> Option<u8> {
+    if key_info & KEY_TYPE_PAIRWISE == 0 {
+        return None;
+    }
+
+    let ack = key_info & KEY_ACK != 0;
+    let mic = key_info & KEY_MIC != 0;
+    let install = key_info & KEY_INSTALL != 0;
+    let secure = key_info & KEY_SECURE != 0;
+
+    match (ack, mic, install, secure) {
+        (true, false, false, false) => Some(1),
+        (false, true, false, false) => Some(2),
+        (true, true, true, true) => Some(3),
+        (false, true, false, true) => Some(4),
+        _ => None,
+    }
+}
+
+/// Parse a captured radiotap+802.11+LLC/SNAP frame and, if it carries an
+/// EAPOL-Key payload belonging to the 4-way handshake, return the
+/// `(bssid, client_mac, frame)` it belongs to.
+#[allow(clippy::unnecessary_cast)] // libc::time_t/suseconds_t are i32 on some platforms
+pub fn parse_eapol_frame(packet: &pcap::Packet) -> Option<(String, String, EapolFrame)> {
+    let data = packet.data;
+
+    let radiotap_len = u16::from_le_bytes(data.get(2..4)?.try_into().ok()?) as usize;
+    let dot11 = data.get(radiotap_len..)?;
+
+    let fc0 = *dot11.first()?;
+    let fc1 = *dot11.get(1)?;
+    let frame_type = (fc0 >> 2) & 0x3;
+    let subtype = (fc0 >> 4) & 0xF;
+    if frame_type != 2 {
+        return None; // only Data frames carry EAPOL
+    }
+
+    let to_ds = fc1 & 0x01 != 0;
+    let from_ds = fc1 & 0x02 != 0;
+    if to_ds && from_ds {
+        return None; // WDS frame, not a normal AP<->STA association
+    }
+
+    let addr1 = mac_string(dot11.get(4..10)?);
+    let addr2 = mac_string(dot11.get(10..16)?);
+
+    let (bssid, client_mac) = if from_ds {
+        (addr2, addr1) // AP (transmitter) -> STA
+    } else if to_ds {
+        (addr1, addr2) // STA (transmitter) -> AP (receiver)
+    } else {
+        return None; // IBSS frame, no BSS to attribute this to
+    };
+
+    let mut header_len = 24;
+    if subtype & 0x08 != 0 {
+        header_len += 2; // QoS control
+    }
+
+    let body = dot11.get(header_len..)?;
+    if body.get(0..6)? != LLC_SNAP_HEADER {
+        return None;
+    }
+    let ethertype = u16::from_be_bytes(body.get(6..8)?.try_into().ok()?);
+    if ethertype != ETHERTYPE_EAPOL {
+        return None;
+    }
+
+    let eapol = body.get(8..)?;
+    let eapol_type = *eapol.get(1)?;
+    if eapol_type != EAPOL_TYPE_KEY {
+        return None;
+    }
+
+    let key = eapol.get(4..)?; // skip version, type, body length
+    let key_info = u16::from_be_bytes(key.get(1..3)?.try_into().ok()?);
+    let replay_counter = u64::from_be_bytes(key.get(5..13)?.try_into().ok()?);
+    let key_nonce: [u8; 32] = key.get(13..45)?.try_into().ok()?;
+    let key_mic: [u8; 16] = key.get(77..93)?.try_into().ok()?;
+
+    let body_length = u16::from_be_bytes(eapol.get(2..4)?.try_into().ok()?) as usize;
+    let eapol_frame = eapol.get(0..4 + body_length)?.to_vec();
+
+    let message_num = classify_eapol_message(key_info)?;
+
+    let frame = EapolFrame {
+        message_num,
+        key_info,
+        replay_counter,
+        key_nonce,
+        key_mic,
+        eapol_frame,
+        raw: data.to_vec(),
+        ts_secs: packet.header.ts.tv_sec as i64,
+        ts_usec: packet.header.ts.tv_usec as i64,
+        orig_len: packet.header.len,
+    };
+
+    Some((bssid, client_mac, frame))
+}
+
+fn mac_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_eapol_message() {
+        assert_eq!(classify_eapol_message(KEY_TYPE_PAIRWISE | KEY_ACK), Some(1));
+        assert_eq!(classify_eapol_message(KEY_TYPE_PAIRWISE | KEY_MIC), Some(2));
+        assert_eq!(
+            classify_eapol_message(KEY_TYPE_PAIRWISE | KEY_ACK | KEY_MIC | KEY_INSTALL | KEY_SECURE),
+            Some(3)
+        );
+        assert_eq!(
+            classify_eapol_message(KEY_TYPE_PAIRWISE | KEY_MIC | KEY_SECURE),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_classify_eapol_message_rejects_group_key_and_malformed() {
+        assert_eq!(classify_eapol_message(KEY_ACK), None); // no pairwise bit - group key
+        assert_eq!(classify_eapol_message(KEY_TYPE_PAIRWISE), None); // no flags set at all
+    }
+
+    /// Build a minimal radiotap + 802.11 data + LLC/SNAP + EAPOL-Key frame,
+    /// with `client_mac` transmitting to `bssid` (to-DS) if `from_ds` is
+    /// false, or the reverse (from-DS) if true.
+    fn build_frame(key_info: u16, from_ds: bool) -> Vec<u8> {
+        const RADIOTAP_LEN: usize = 8;
+        const CLIENT_MAC: [u8; 6] = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66];
+        const BSSID_MAC: [u8; 6] = [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+
+        let key_body_len: usize = 95;
+        let mut eapol = vec![0u8; 4 + key_body_len];
+        eapol[0] = 2; // descriptor type (RSN)
+        eapol[1] = EAPOL_TYPE_KEY;
+        eapol[2..4].copy_from_slice(&(key_body_len as u16).to_be_bytes());
+        eapol[4 + 1..4 + 3].copy_from_slice(&key_info.to_be_bytes());
+        eapol[4 + 5..4 + 13].copy_from_slice(&1u64.to_be_bytes()); // replay counter
+        eapol[4 + 13..4 + 45].fill(0x42); // key nonce
+        eapol[4 + 77..4 + 93].fill(0x99); // key MIC
+
+        let mut body = LLC_SNAP_HEADER.to_vec();
+        body.extend_from_slice(&ETHERTYPE_EAPOL.to_be_bytes());
+        body.extend_from_slice(&eapol);
+
+        let mut dot11 = vec![0u8; 24];
+        dot11[0] = 0x08; // frame_type = Data, subtype = 0
+        dot11[1] = if from_ds { 0x02 } else { 0x01 };
+        if from_ds {
+            dot11[4..10].copy_from_slice(&CLIENT_MAC); // addr1 = receiver (STA)
+            dot11[10..16].copy_from_slice(&BSSID_MAC); // addr2 = transmitter (AP)
+        } else {
+            dot11[4..10].copy_from_slice(&BSSID_MAC); // addr1 = receiver (AP)
+            dot11[10..16].copy_from_slice(&CLIENT_MAC); // addr2 = transmitter (STA)
+        }
+        dot11.extend_from_slice(&body);
+
+        let mut frame = vec![0u8; RADIOTAP_LEN];
+        frame[2..4].copy_from_slice(&(RADIOTAP_LEN as u16).to_le_bytes());
+        frame.extend_from_slice(&dot11);
+        frame
+    }
+
+    fn parse(data: &[u8]) -> Option<(String, String, EapolFrame)> {
+        let header = pcap::PacketHeader {
+            ts: libc::timeval { tv_sec: 0, tv_usec: 0 },
+            caplen: data.len() as u32,
+            len: data.len() as u32,
+        };
+        let packet = pcap::Packet::new(&header, data);
+        parse_eapol_frame(&packet)
+    }
+
+    #[test]
+    fn test_parse_eapol_frame_message1_from_ap() {
+        let data = build_frame(KEY_TYPE_PAIRWISE | KEY_ACK, true);
+        let (bssid, client_mac, frame) = parse(&data).expect("should parse a valid message 1");
+
+        assert_eq!(bssid, "aa:bb:cc:dd:ee:ff");
+        assert_eq!(client_mac, "11:22:33:44:55:66");
+        assert_eq!(frame.message_num, 1);
+        assert_eq!(frame.replay_counter, 1);
+        assert_eq!(frame.key_nonce, [0x42; 32]);
+        assert_eq!(frame.key_mic, [0x99; 16]);
+    }
+
+    #[test]
+    fn test_parse_eapol_frame_message2_from_station() {
+        let data = build_frame(KEY_TYPE_PAIRWISE | KEY_MIC, false);
+        let (bssid, client_mac, frame) = parse(&data).expect("should parse a valid message 2");
+
+        assert_eq!(bssid, "aa:bb:cc:dd:ee:ff");
+        assert_eq!(client_mac, "11:22:33:44:55:66");
+        assert_eq!(frame.message_num, 2);
+    }
+
+    #[test]
+    fn test_parse_eapol_frame_rejects_non_data_frame() {
+        let mut data = build_frame(KEY_TYPE_PAIRWISE | KEY_ACK, true);
+        data[8] = 0x00; // frame_type = Management, not Data
+        assert!(parse(&data).is_none());
+    }
+
+    #[test]
+    fn test_parse_eapol_frame_rejects_wds_frame() {
+        let mut data = build_frame(KEY_TYPE_PAIRWISE | KEY_ACK, true);
+        data[9] = 0x03; // to_ds and from_ds both set
+        assert!(parse(&data).is_none());
+    }
+
+    #[test]
+    fn test_parse_eapol_frame_rejects_non_eapol_ethertype() {
+        let mut data = build_frame(KEY_TYPE_PAIRWISE | KEY_ACK, true);
+        let ethertype_offset = 8 + 24 + 6;
+        data[ethertype_offset..ethertype_offset + 2].copy_from_slice(&0x0800u16.to_be_bytes());
+        assert!(parse(&data).is_none());
+    }
+}
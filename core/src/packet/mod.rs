@@ -0,0 +1,9 @@
+pub mod capture;
+mod eapol;
+
+// Handshake-capture and EAPOL-frame types the CLI will wire up; unused for
+// now since main() doesn't call into this module yet.
+#[allow(unused_imports)]
+pub use capture::{CapturedHandshake, PacketCapture};
+#[allow(unused_imports)]
+pub use eapol::{classify_eapol_message, EapolFrame, MIC_FIELD_RANGE};
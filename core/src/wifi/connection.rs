@@ -0,0 +1,282 @@
+//! Client-mode association driven by wpa_supplicant's control interface
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use super::scanner::{Network, SecurityType};
+
+/// Default directory wpa_supplicant exposes its per-interface control socket in.
+const CTRL_SOCKET_DIR: &str = "/var/run/wpa_supplicant";
+
+/// Credentials for associating with a network, matching its `SecurityType`.
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    Open,
+    Wep { key: String },
+    Psk { passphrase: String },
+}
+
+/// Association state as reported by wpa_supplicant's `STATUS` command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConnectionState {
+    Disconnected,
+    Scanning,
+    Associating,
+    Associated,
+    FourWayHandshake,
+    GroupHandshake,
+    Completed,
+}
+
+/// A specific, known reason a connection attempt did not reach `Completed`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConnectFailure {
+    WrongPassword,
+    ApNotFound,
+    Timeout,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectResult {
+    pub network_id: u32,
+    pub state: ConnectionState,
+    pub failure: Option<ConnectFailure>,
+}
+
+/// Wraps a wpa_supplicant control-socket session for one interface.
+pub struct WifiConnection {
+    interface: String,
+    ctrl: wpactrl::Client,
+    poll_interval: Duration,
+    connect_timeout: Duration,
+}
+
+impl WifiConnection {
+    pub fn new(interface: impl Into<String>) -> Result<Self> {
+        let interface = interface.into();
+        let ctrl_path = format!("{CTRL_SOCKET_DIR}/{interface}");
+        let ctrl = wpactrl::Client::builder()
+            .ctrl_path(&ctrl_path)
+            .open()
+            .with_context(|| format!("failed to open wpa_supplicant control socket at {ctrl_path}"))?;
+
+        Ok(Self {
+            interface,
+            ctrl,
+            poll_interval: Duration::from_millis(500),
+            connect_timeout: Duration::from_secs(15),
+        })
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Add a network block for `network`, select it, and poll until settled.
+    pub async fn connect(&mut self, network: &Network, credentials: Credentials) -> Result<ConnectResult> {
+        validate_credentials(&network.security_type, &credentials)?;
+
+        let network_id = self.add_network()?;
+        self.set_network_str(network_id, "ssid", &network.ssid)?;
+
+        match &credentials {
+            Credentials::Open => {
+                self.set_network_raw(network_id, "key_mgmt", "NONE")?;
+            }
+            Credentials::Wep { key } => {
+                self.set_network_raw(network_id, "key_mgmt", "NONE")?;
+                self.set_network_str(network_id, "wep_key0", key)?;
+            }
+            Credentials::Psk { passphrase } => {
+                self.set_network_raw(network_id, "key_mgmt", key_mgmt_for(&network.security_type))?;
+                self.set_network_str(network_id, "psk", passphrase)?;
+            }
+        }
+
+        self.request(&format!("ENABLE_NETWORK {network_id}"))?;
+        self.request(&format!("SELECT_NETWORK {network_id}"))?;
+
+        let (state, failure) = self.poll_until_settled(network_id).await?;
+
+        Ok(ConnectResult {
+            network_id,
+            state,
+            failure,
+        })
+    }
+
+    /// Disconnect from the currently associated network without forgetting it.
+    pub async fn disconnect(&mut self) -> Result<()> {
+        self.request("DISCONNECT")?;
+        Ok(())
+    }
+
+    /// Remove a previously added network block entirely.
+    pub async fn forget(&mut self, network_id: u32) -> Result<()> {
+        self.request(&format!("REMOVE_NETWORK {network_id}"))?;
+        self.request("SAVE_CONFIG")?;
+        Ok(())
+    }
+
+    /// Current association state, independent of any in-progress `connect`.
+    pub async fn status(&mut self) -> Result<ConnectionState> {
+        let status = self.request("STATUS")?;
+        Ok(parse_wpa_state(&status))
+    }
+
+    async fn poll_until_settled(
+        &mut self,
+        network_id: u32,
+    ) -> Result<(ConnectionState, Option<ConnectFailure>)> {
+        let deadline = Instant::now() + self.connect_timeout;
+        let mut seen_association = false;
+
+        loop {
+            let status = self.request("STATUS")?;
+            let state = parse_wpa_state(&status);
+
+            if state == ConnectionState::Completed {
+                return Ok((state, None));
+            }
+
+            if matches!(
+                state,
+                ConnectionState::Associating
+                    | ConnectionState::Associated
+                    | ConnectionState::FourWayHandshake
+            ) {
+                seen_association = true;
+            }
+
+            // wpa_supplicant temp-disables a network after repeated auth
+            // failures; seeing our own network id come back disabled after
+            // we'd already associated means the PSK/key was wrong.
+            if seen_association && self.network_is_disabled(network_id)? {
+                return Ok((state, Some(ConnectFailure::WrongPassword)));
+            }
+
+            if Instant::now() >= deadline {
+                let failure = if seen_association {
+                    ConnectFailure::Timeout
+                } else {
+                    ConnectFailure::ApNotFound
+                };
+                return Ok((state, Some(failure)));
+            }
+
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    fn network_is_disabled(&mut self, network_id: u32) -> Result<bool> {
+        let list = self.request("LIST_NETWORKS")?;
+        let id = network_id.to_string();
+
+        Ok(list.lines().any(|line| {
+            let mut fields = line.split('\t');
+            fields.next() == Some(id.as_str())
+                && fields.nth(2).is_some_and(|flags| flags.contains("DISABLED"))
+        }))
+    }
+
+    fn add_network(&mut self) -> Result<u32> {
+        let reply = self.request("ADD_NETWORK")?;
+        reply
+            .trim()
+            .parse()
+            .with_context(|| format!("unexpected ADD_NETWORK reply: {reply}"))
+    }
+
+    fn set_network_str(&mut self, network_id: u32, key: &str, value: &str) -> Result<()> {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        self.request(&format!("SET_NETWORK {network_id} {key} \"{escaped}\""))?;
+        Ok(())
+    }
+
+    fn set_network_raw(&mut self, network_id: u32, key: &str, value: &str) -> Result<()> {
+        self.request(&format!("SET_NETWORK {network_id} {key} {value}"))?;
+        Ok(())
+    }
+
+    fn request(&mut self, cmd: &str) -> Result<String> {
+        self.ctrl
+            .request(cmd)
+            .with_context(|| format!("wpa_supplicant request '{cmd}' failed on {}", self.interface))
+    }
+}
+
+/// `key_mgmt` value for a PSK network block, mirroring `configure_ap`'s hostapd renderer.
+fn key_mgmt_for(security_type: &SecurityType) -> &'static str {
+    match security_type {
+        SecurityType::WPA3 => "SAE",
+        SecurityType::WPA2WPA3 => "SAE WPA-PSK",
+        _ => "WPA-PSK",
+    }
+}
+
+fn validate_credentials(security_type: &SecurityType, credentials: &Credentials) -> Result<()> {
+    let matches = matches!(
+        (security_type, credentials),
+        (SecurityType::Open, Credentials::Open)
+            | (SecurityType::WEP, Credentials::Wep { .. })
+            | (SecurityType::WPA, Credentials::Psk { .. })
+            | (SecurityType::WPA2, Credentials::Psk { .. })
+            | (SecurityType::WPA3, Credentials::Psk { .. })
+            | (SecurityType::WPA2WPA3, Credentials::Psk { .. })
+    );
+
+    if matches {
+        Ok(())
+    } else {
+        anyhow::bail!("credentials do not match the network's security type {security_type:?}")
+    }
+}
+
+fn parse_wpa_state(status: &str) -> ConnectionState {
+    for line in status.lines() {
+        if let Some(state) = line.strip_prefix("wpa_state=") {
+            return match state {
+                "SCANNING" => ConnectionState::Scanning,
+                "ASSOCIATING" => ConnectionState::Associating,
+                "ASSOCIATED" => ConnectionState::Associated,
+                "4WAY_HANDSHAKE" => ConnectionState::FourWayHandshake,
+                "GROUP_HANDSHAKE" => ConnectionState::GroupHandshake,
+                "COMPLETED" => ConnectionState::Completed,
+                _ => ConnectionState::Disconnected,
+            };
+        }
+    }
+
+    ConnectionState::Disconnected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wpa_state() {
+        let status = "bssid=aa:bb:cc:dd:ee:ff\nwpa_state=COMPLETED\nssid=test\n";
+        assert_eq!(parse_wpa_state(status), ConnectionState::Completed);
+    }
+
+    #[test]
+    fn test_validate_credentials_rejects_mismatch() {
+        let err = validate_credentials(&SecurityType::WPA2, &Credentials::Open);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_validate_credentials_accepts_matching_psk() {
+        let result = validate_credentials(
+            &SecurityType::WPA2,
+            &Credentials::Psk {
+                passphrase: "supersecret".to_string(),
+            },
+        );
+        assert!(result.is_ok());
+    }
+}
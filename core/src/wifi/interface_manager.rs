@@ -1,9 +1,13 @@
 //! WiFi interface management
 
 use anyhow::{Result, Context};
-use std::process::Command;
+use std::collections::HashMap;
+use std::process::{Child, Command};
+use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 
+use super::scanner::SecurityType;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WifiInterface {
     pub name: String,
@@ -13,11 +17,25 @@ pub struct WifiInterface {
     pub driver: Option<String>,
 }
 
-pub struct InterfaceManager;
+/// Configuration for standing an interface up as an access point via hostapd.
+#[derive(Debug, Clone)]
+pub struct ApConfig {
+    pub ssid: String,
+    pub channel: u8,
+    pub security_type: SecurityType,
+    pub passphrase: Option<String>,
+    pub hidden: bool,
+}
+
+pub struct InterfaceManager {
+    ap_processes: Mutex<HashMap<String, Child>>,
+}
 
 impl InterfaceManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            ap_processes: Mutex::new(HashMap::new()),
+        }
     }
 
     /// List all available WiFi interfaces
@@ -41,7 +59,7 @@ impl InterfaceManager {
     #[cfg(target_os = "linux")]
     fn list_interfaces_linux(&self) -> Result<Vec<WifiInterface>> {
         let output = Command::new("iw")
-            .args(&["dev"])
+            .args(["dev"])
             .output()
             .context("Failed to execute 'iw dev' command")?;
 
@@ -80,7 +98,7 @@ impl InterfaceManager {
     #[cfg(target_os = "macos")]
     fn list_interfaces_macos(&self) -> Result<Vec<WifiInterface>> {
         let output = Command::new("networksetup")
-            .args(&["-listallhardwareports"])
+            .args(["-listallhardwareports"])
             .output()
             .context("Failed to execute networksetup command")?;
 
@@ -117,19 +135,19 @@ impl InterfaceManager {
     pub fn enable_monitor_mode(&self, interface: &str) -> Result<()> {
         // Bring interface down
         Command::new("ip")
-            .args(&["link", "set", interface, "down"])
+            .args(["link", "set", interface, "down"])
             .output()
             .context("Failed to bring interface down")?;
 
         // Set monitor mode
         Command::new("iw")
-            .args(&[interface, "set", "monitor", "control"])
+            .args([interface, "set", "monitor", "control"])
             .output()
             .context("Failed to set monitor mode")?;
 
         // Bring interface up
         Command::new("ip")
-            .args(&["link", "set", interface, "up"])
+            .args(["link", "set", interface, "up"])
             .output()
             .context("Failed to bring interface up")?;
 
@@ -140,15 +158,15 @@ impl InterfaceManager {
     #[cfg(target_os = "linux")]
     pub fn disable_monitor_mode(&self, interface: &str) -> Result<()> {
         Command::new("ip")
-            .args(&["link", "set", interface, "down"])
+            .args(["link", "set", interface, "down"])
             .output()?;
 
         Command::new("iw")
-            .args(&[interface, "set", "type", "managed"])
+            .args([interface, "set", "type", "managed"])
             .output()?;
 
         Command::new("ip")
-            .args(&["link", "set", interface, "up"])
+            .args(["link", "set", interface, "up"])
             .output()?;
 
         Ok(())
@@ -159,7 +177,7 @@ impl InterfaceManager {
         #[cfg(target_os = "linux")]
         {
             let output = Command::new("iw")
-                .args(&["dev", interface, "info"])
+                .args(["dev", interface, "info"])
                 .output()?;
 
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -184,6 +202,94 @@ impl InterfaceManager {
             driver: None,
         })
     }
+
+    /// Stand `interface` up as an access point via hostapd
+    #[cfg(target_os = "linux")]
+    pub fn configure_ap(&self, interface: &str, cfg: ApConfig) -> Result<()> {
+        Command::new("ip")
+            .args(["link", "set", interface, "down"])
+            .output()
+            .context("Failed to bring interface down")?;
+
+        Command::new("iw")
+            .args([interface, "set", "type", "__ap"])
+            .output()
+            .context("Failed to set AP mode")?;
+
+        Command::new("ip")
+            .args(["link", "set", interface, "up"])
+            .output()
+            .context("Failed to bring interface up")?;
+
+        let conf_path = hostapd_conf_path(interface);
+        std::fs::write(&conf_path, render_hostapd_config(interface, &cfg))
+            .with_context(|| format!("failed to write hostapd config to {conf_path}"))?;
+
+        let child = Command::new("hostapd")
+            .arg(&conf_path)
+            .spawn()
+            .context("failed to launch hostapd")?;
+
+        self.ap_processes
+            .lock()
+            .unwrap()
+            .insert(interface.to_string(), child);
+
+        Ok(())
+    }
+
+    /// Stop the hostapd process started by `configure_ap`
+    #[cfg(target_os = "linux")]
+    pub fn teardown_ap(&self, interface: &str) -> Result<()> {
+        if let Some(mut child) = self.ap_processes.lock().unwrap().remove(interface) {
+            child.kill().context("failed to stop hostapd")?;
+            child.wait().context("failed to reap hostapd process")?;
+        }
+
+        self.disable_monitor_mode(interface)
+    }
+}
+
+fn hostapd_conf_path(interface: &str) -> String {
+    format!("/tmp/hostapd-{interface}.conf")
+}
+
+fn render_hostapd_config(interface: &str, cfg: &ApConfig) -> String {
+    let hw_mode = if cfg.channel <= 14 { "g" } else { "a" };
+
+    let mut lines = vec![
+        format!("interface={interface}"),
+        "driver=nl80211".to_string(),
+        format!("ssid={}", cfg.ssid),
+        format!("hw_mode={hw_mode}"),
+        format!("channel={}", cfg.channel),
+        format!("ignore_broadcast_ssid={}", cfg.hidden as u8),
+    ];
+
+    match (&cfg.security_type, cfg.passphrase.as_deref()) {
+        (SecurityType::WPA3, Some(passphrase)) | (SecurityType::WPA2WPA3, Some(passphrase)) => {
+            lines.push("wpa=2".to_string());
+            lines.push(format!("wpa_passphrase={passphrase}"));
+            lines.push("wpa_key_mgmt=SAE WPA-PSK".to_string());
+            lines.push("rsn_pairwise=CCMP".to_string());
+            lines.push("ieee80211w=2".to_string());
+        }
+        (SecurityType::WPA, Some(passphrase)) | (SecurityType::WPA2, Some(passphrase)) => {
+            lines.push("wpa=2".to_string());
+            lines.push(format!("wpa_passphrase={passphrase}"));
+            lines.push("wpa_key_mgmt=WPA-PSK".to_string());
+            lines.push("rsn_pairwise=CCMP".to_string());
+        }
+        (SecurityType::Open, _) => {}
+        (security_type, None) => {
+            tracing::warn!("no passphrase given for {security_type:?} AP, falling back to open");
+        }
+        (security_type, Some(_)) => {
+            tracing::warn!("unsupported AP security type {security_type:?}, falling back to open");
+        }
+    }
+
+    lines.join("\n") + "\n"
 }
 
 #[cfg(test)]
@@ -194,8 +300,42 @@ mod tests {
     fn test_list_interfaces() {
         let manager = InterfaceManager::new();
         let result = manager.list_interfaces();
-        
+
         // Should not fail even if no interfaces found
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_render_hostapd_config_open() {
+        let cfg = ApConfig {
+            ssid: "TestAP".to_string(),
+            channel: 6,
+            security_type: SecurityType::Open,
+            passphrase: None,
+            hidden: false,
+        };
+
+        let config = render_hostapd_config("wlan0", &cfg);
+        assert!(config.contains("interface=wlan0"));
+        assert!(config.contains("ssid=TestAP"));
+        assert!(config.contains("hw_mode=g"));
+        assert!(!config.contains("wpa="));
+    }
+
+    #[test]
+    fn test_render_hostapd_config_wpa2_hidden() {
+        let cfg = ApConfig {
+            ssid: "HiddenAP".to_string(),
+            channel: 44,
+            security_type: SecurityType::WPA2,
+            passphrase: Some("supersecret".to_string()),
+            hidden: true,
+        };
+
+        let config = render_hostapd_config("wlan0", &cfg);
+        assert!(config.contains("hw_mode=a"));
+        assert!(config.contains("ignore_broadcast_ssid=1"));
+        assert!(config.contains("wpa_passphrase=supersecret"));
+        assert!(config.contains("wpa_key_mgmt=WPA-PSK"));
+    }
 }
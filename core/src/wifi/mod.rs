@@ -1,5 +1,15 @@
 pub mod scanner;
 pub mod interface_manager;
+pub mod connection;
 
-pub use scanner::{WiFiScanner, Network, SecurityType, SecurityReport};
-pub use interface_manager::{InterfaceManager, WifiInterface};
+// Scanning, association, and interface-management types the CLI will wire
+// up; unused for now since main() doesn't call into this module yet.
+#[allow(unused_imports)]
+pub use scanner::{
+    ActiveScanResult, Bssid, ConnectHistory, Network, NetworkScore, SecurityReport, SecurityType,
+    WiFiScanner,
+};
+#[allow(unused_imports)]
+pub use interface_manager::{ApConfig, InterfaceManager, WifiInterface};
+#[allow(unused_imports)]
+pub use connection::{ConnectFailure, ConnectResult, ConnectionState, Credentials, WifiConnection};
@@ -2,10 +2,14 @@
 
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::process::Command;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
+/// A network's BSSID, e.g. `"aa:bb:cc:dd:ee:ff"`.
+pub type Bssid = String;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Network {
     pub ssid: String,
@@ -23,6 +27,7 @@ pub struct Network {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(clippy::upper_case_acronyms)] // WEP/WPA are the standard names, not abbreviations to rename
 pub enum SecurityType {
     Open,
     WEP,
@@ -102,11 +107,73 @@ impl WiFiScanner {
         }
     }
 
+    /// Perform a passive scan plus directed probe requests for `ssids`
+    pub async fn scan_active(&self, ssids: &[String]) -> Result<ActiveScanResult> {
+        let mut networks = self.scan_networks().await?;
+        let mut observed = HashSet::new();
+
+        for ssid in ssids {
+            let probed = self.probe_for_ssid(ssid).await?;
+
+            for probe in probed {
+                if probe.ssid.is_empty() {
+                    continue;
+                }
+
+                match networks.iter_mut().find(|network| network.bssid == probe.bssid) {
+                    Some(existing) if existing.ssid.is_empty() => {
+                        existing.ssid = probe.ssid.clone();
+                        existing.hidden = false;
+                    }
+                    Some(_) => {}
+                    None => networks.push(probe.clone()),
+                }
+
+                if probe.ssid == *ssid {
+                    observed.insert(ssid.clone());
+                }
+            }
+        }
+
+        Ok(ActiveScanResult {
+            networks,
+            ssids_observed: observed.len(),
+        })
+    }
+
+    /// Issue a directed probe request for `ssid`
+    #[cfg(target_os = "linux")]
+    async fn probe_for_ssid(&self, ssid: &str) -> Result<Vec<Network>> {
+        let _output = Command::new("iw")
+            .args([&self.interface, "scan", "ssid", ssid])
+            .output()
+            .context("Failed to trigger directed probe scan")?;
+
+        sleep(self.scan_duration).await;
+
+        let output = Command::new("iw")
+            .args([&self.interface, "scan", "dump"])
+            .output()
+            .context("Failed to get directed scan results")?;
+
+        if !output.status.success() {
+            anyhow::bail!("Directed scan failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        self.parse_iw_scan_results(&stdout)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn probe_for_ssid(&self, _ssid: &str) -> Result<Vec<Network>> {
+        anyhow::bail!("Active probe scanning for hidden SSIDs is only supported on Linux")
+    }
+
     #[cfg(target_os = "linux")]
     async fn scan_networks_linux(&self) -> Result<Vec<Network>> {
         // Trigger scan
         let _output = Command::new("iw")
-            .args(&[&self.interface, "scan"])
+            .args([&self.interface, "scan"])
             .output()
             .context("Failed to trigger WiFi scan")?;
 
@@ -115,7 +182,7 @@ impl WiFiScanner {
 
         // Get scan results
         let output = Command::new("iw")
-            .args(&[&self.interface, "scan", "dump"])
+            .args([&self.interface, "scan", "dump"])
             .output()
             .context("Failed to get scan results")?;
 
@@ -132,7 +199,7 @@ impl WiFiScanner {
     #[cfg(target_os = "macos")]
     async fn scan_networks_macos(&self) -> Result<Vec<Network>> {
         let output = Command::new("/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport")
-            .args(&["-s"])
+            .args(["-s"])
             .output()
             .context("Failed to scan WiFi networks")?;
 
@@ -356,10 +423,112 @@ impl WiFiScanner {
             _ => 0,
         }
     }
+
+    /// How long a recorded connection/auth failure keeps penalizing a BSSID.
+    const FAILURE_WINDOW: Duration = Duration::from_secs(300);
+    const FAILURE_PENALTY: i32 = 1000;
+
+    /// Rank `networks` and return the single best candidate to associate with
+    pub fn select_best<'a>(
+        &self,
+        networks: &'a [Network],
+        history: &ConnectHistory,
+    ) -> Option<&'a Network> {
+        self.score_candidates(networks, history)
+            .into_iter()
+            .max_by_key(|candidate| candidate.score)
+            .map(|candidate| candidate.network)
+    }
+
+    /// Per-candidate scores behind `select_best`
+    pub fn score_candidates<'a>(
+        &self,
+        networks: &'a [Network],
+        history: &ConnectHistory,
+    ) -> Vec<NetworkScore<'a>> {
+        let mut best_per_ssid: HashMap<&str, NetworkScore<'a>> = HashMap::new();
+
+        for network in networks {
+            let candidate = NetworkScore {
+                network,
+                score: Self::score_network(network, history),
+            };
+
+            best_per_ssid
+                .entry(network.ssid.as_str())
+                .and_modify(|existing| {
+                    if candidate.score > existing.score {
+                        *existing = candidate.clone();
+                    }
+                })
+                .or_insert(candidate);
+        }
+
+        let mut scores: Vec<_> = best_per_ssid.into_values().collect();
+        scores.sort_by_key(|candidate| std::cmp::Reverse(candidate.score));
+        scores
+    }
+
+    fn score_network(network: &Network, history: &ConnectHistory) -> i32 {
+        let signal_quality = Self::signal_quality(network.signal_strength);
+        let security_bonus = network.security_type.security_level() as i32 * 5;
+
+        let mut score = signal_quality + security_bonus;
+        if history.recent_failure(&network.bssid, Self::FAILURE_WINDOW) {
+            score -= Self::FAILURE_PENALTY;
+        }
+
+        score
+    }
+
+    /// Map RSSI to a 0-100 quality value
+    fn signal_quality(signal_strength: i16) -> i32 {
+        const MIN_RSSI: i32 = -90;
+        const MAX_RSSI: i32 = -35;
+
+        let clamped = (signal_strength as i32).clamp(MIN_RSSI, MAX_RSSI);
+        (clamped - MIN_RSSI) * 100 / (MAX_RSSI - MIN_RSSI)
+    }
+}
+
+/// A candidate network along with the score `select_best` ranked it with.
+#[derive(Debug, Clone)]
+pub struct NetworkScore<'a> {
+    pub network: &'a Network,
+    pub score: i32,
+}
+
+/// Tracks recent connection/auth failures per BSSID
+#[derive(Debug, Default)]
+pub struct ConnectHistory {
+    failures: HashMap<Bssid, Instant>,
+}
+
+impl ConnectHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_failure(&mut self, bssid: impl Into<Bssid>) {
+        self.failures.insert(bssid.into(), Instant::now());
+    }
+
+    fn recent_failure(&self, bssid: &str, window: Duration) -> bool {
+        self.failures
+            .get(bssid)
+            .is_some_and(|recorded_at| recorded_at.elapsed() < window)
+    }
+}
+
+/// Result of `WiFiScanner::scan_active`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActiveScanResult {
+    pub networks: Vec<Network>,
+    pub ssids_observed: usize,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
-pub struct Security Report {
+pub struct SecurityReport {
     pub wps_enabled: bool,
     pub crackability_score: u8,
     pub estimated_crack_time: String,
@@ -388,4 +557,62 @@ mod tests {
         assert_eq!(SecurityType::from_str("WEP"), SecurityType::WEP);
         assert_eq!(SecurityType::from_str("Open"), SecurityType::Open);
     }
+
+    fn test_network(ssid: &str, bssid: &str, signal_strength: i16, security_type: SecurityType) -> Network {
+        Network {
+            ssid: ssid.to_string(),
+            bssid: bssid.to_string(),
+            channel: 0,
+            frequency: 0,
+            signal_strength,
+            security_type,
+            encryption: None,
+            authentication: None,
+            wps_enabled: false,
+            wps_locked: false,
+            hidden: false,
+            clients: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_best_prefers_stronger_security_at_equal_signal() {
+        let scanner = WiFiScanner::new("wlan0".to_string());
+        let networks = vec![
+            test_network("open-net", "aa:aa:aa:aa:aa:01", -50, SecurityType::Open),
+            test_network("wpa3-net", "aa:aa:aa:aa:aa:02", -50, SecurityType::WPA3),
+        ];
+        let history = ConnectHistory::new();
+
+        let best = scanner.select_best(&networks, &history).unwrap();
+        assert_eq!(best.ssid, "wpa3-net");
+    }
+
+    #[test]
+    fn test_select_best_penalizes_recent_failure() {
+        let scanner = WiFiScanner::new("wlan0".to_string());
+        let networks = vec![
+            test_network("strong-flaky", "aa:aa:aa:aa:aa:01", -40, SecurityType::WPA2),
+            test_network("weaker-reliable", "aa:aa:aa:aa:aa:02", -70, SecurityType::WPA2),
+        ];
+        let mut history = ConnectHistory::new();
+        history.record_failure("aa:aa:aa:aa:aa:01");
+
+        let best = scanner.select_best(&networks, &history).unwrap();
+        assert_eq!(best.ssid, "weaker-reliable");
+    }
+
+    #[test]
+    fn test_score_candidates_dedupes_by_ssid() {
+        let scanner = WiFiScanner::new("wlan0".to_string());
+        let networks = vec![
+            test_network("dup-ssid", "aa:aa:aa:aa:aa:01", -80, SecurityType::WPA2),
+            test_network("dup-ssid", "aa:aa:aa:aa:aa:02", -40, SecurityType::WPA2),
+        ];
+        let history = ConnectHistory::new();
+
+        let scores = scanner.score_candidates(&networks, &history);
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].network.bssid, "aa:aa:aa:aa:aa:02");
+    }
 }